@@ -1,12 +1,52 @@
 #![feature(allocator_api)]
 
+#[cfg(unix)]
+mod spill;
+#[cfg(unix)]
+pub use spill::SpillAllocator;
+
 use std::{
-    alloc::{realloc, Allocator, Global, Layout},
-    ops::{Deref, DerefMut, Index, IndexMut},
+    alloc::{Allocator, Global, Layout},
+    error::Error,
+    fmt, mem,
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
     ptr::NonNull,
     slice::{from_raw_parts, from_raw_parts_mut, Iter, IterMut},
 };
 
+/// The error returned by the `try_*` family of methods when an allocation
+/// cannot be satisfied, either because the requested capacity is too large
+/// to express or because the allocator itself failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds `isize::MAX` bytes.
+    CapacityOverflow,
+    /// The allocator returned an error for the given layout.
+    AllocError {
+        /// The layout that the allocator failed to provide.
+        layout: Layout,
+    },
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => {
+                write!(f, "memory allocation failed because the computed capacity exceeded the collection's maximum")
+            }
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl Error for TryReserveError {}
+
+/// `#[repr(C)]` so the layout is stable for FFI consumers that reconstruct a
+/// `Vek` via [`Vek::from_raw_parts`] or tear one down via
+/// [`Vek::into_raw_parts`].
+#[repr(C)]
 pub struct Vek<T, A = Global>
 where
     A: Allocator,
@@ -26,12 +66,104 @@ impl<T> Vek<T, Global> {
             alloc: Global,
         }
     }
+
+    /// Like [`new`](Self::new), but pre-allocates room for at least
+    /// `capacity` elements using the global allocator.
+    pub fn with_capacity(capacity: usize) -> Vek<T> {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    /// Reconstructs a `Vek` previously decomposed with
+    /// [`into_raw_parts`](Self::into_raw_parts).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by the global allocator with a layout
+    /// matching `Layout::array::<T>(capacity)` (for zero-sized `T`, any
+    /// non-null, well-aligned pointer is accepted), `length` must be `<=
+    /// capacity`, and the first `length` elements must be properly
+    /// initialized values of `T`.
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> Vek<T> {
+        Self::from_raw_parts_in(ptr, length, capacity, Global)
+    }
+
+    /// Decomposes the `Vek` into its raw parts: a pointer to the buffer, the
+    /// length, and the capacity.
+    ///
+    /// No destructors run and the allocation is not freed; the caller takes
+    /// ownership of it, typically to hand it across an FFI boundary and
+    /// later reconstruct it with [`from_raw_parts`](Self::from_raw_parts).
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let (ptr, len, capacity, _alloc) = self.into_raw_parts_in();
+        (ptr, len, capacity)
+    }
 }
 
 impl<T, A> Vek<T, A>
 where
     A: Allocator,
 {
+    /// Creates an empty `Vek` that will allocate through `alloc` instead of
+    /// the global allocator.
+    pub fn new_in(alloc: A) -> Vek<T, A> {
+        Vek {
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity: 0,
+            alloc,
+        }
+    }
+
+    /// Like [`new_in`](Self::new_in), but pre-allocates room for at least
+    /// `capacity` elements.
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Vek<T, A> {
+        let mut vek = Self::new_in(alloc);
+        if capacity > 0 {
+            vek.reserve_exact(capacity);
+        }
+        vek
+    }
+
+    /// Reconstructs a `Vek` previously decomposed with
+    /// [`into_raw_parts_in`](Self::into_raw_parts_in).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by `alloc` with a layout matching
+    /// `Layout::array::<T>(capacity)` (for zero-sized `T`, any non-null,
+    /// well-aligned pointer is accepted), `length` must be `<= capacity`, and
+    /// the first `length` elements must be properly initialized values of
+    /// `T`.
+    pub unsafe fn from_raw_parts_in(
+        ptr: *mut T,
+        length: usize,
+        capacity: usize,
+        alloc: A,
+    ) -> Vek<T, A> {
+        Vek {
+            ptr: NonNull::new_unchecked(ptr),
+            len: length,
+            capacity,
+            alloc,
+        }
+    }
+
+    /// Decomposes the `Vek` into its raw parts: a pointer to the buffer, the
+    /// length, the capacity, and the allocator.
+    ///
+    /// No destructors run and the allocation is not freed; the caller takes
+    /// ownership of it, typically to hand it across an FFI boundary and
+    /// later reconstruct it with
+    /// [`from_raw_parts_in`](Self::from_raw_parts_in).
+    pub fn into_raw_parts_in(self) -> (*mut T, usize, usize, A) {
+        let this = mem::ManuallyDrop::new(self);
+        let ptr = this.ptr.as_ptr();
+        let len = this.len;
+        let capacity = this.capacity;
+        let alloc = unsafe { std::ptr::read(&this.alloc) };
+        (ptr, len, capacity, alloc)
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.len
@@ -44,15 +176,40 @@ where
 
     #[inline]
     pub fn capacity(&self) -> usize {
-        self.capacity
+        if Self::is_zst() {
+            usize::MAX
+        } else {
+            self.capacity
+        }
+    }
+
+    /// Zero-sized `T` never needs a real allocation: every `Vek<T>` already
+    /// holds infinitely many of them for free, so capacity is reported as
+    /// [`usize::MAX`] and `try_grow`/`try_realloc` become no-ops.
+    #[inline]
+    fn is_zst() -> bool {
+        std::mem::size_of::<T>() == 0
     }
 
     pub fn push(&mut self, value: T) {
-        self.grow(self.len + 1);
+        self.try_push(value)
+            .unwrap_or_else(|_| panic!("Vek length or allocation overflow"))
+    }
+
+    /// Like [`push`](Self::push), but reports allocation failure instead of
+    /// panicking, handing `value` back to the caller instead of dropping it.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        let Some(need) = self.len.checked_add(1) else {
+            return Err(value);
+        };
+        if self.try_grow(need).is_err() {
+            return Err(value);
+        }
         unsafe {
             self.ptr.as_ptr().add(self.len).write(value);
             self.len += 1;
         }
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -65,18 +222,36 @@ where
         None
     }
 
-    fn grow(&mut self, need: usize) {
+    /// Computes the next amortized capacity for at least `need` elements,
+    /// growing geometrically (doubling) so that repeated pushes are `O(1)`
+    /// amortized. Doubling saturates to `usize::MAX` instead of wrapping, so
+    /// a huge current capacity can
+    /// never produce a smaller-than-expected one; the actual byte-size
+    /// guard against exceeding `isize::MAX` happens in `try_realloc` via
+    /// `Layout::array`, which is what ultimately turns an unreasonable
+    /// `new_cap` into [`TryReserveError::CapacityOverflow`].
+    fn try_grow(&mut self, need: usize) -> Result<(), TryReserveError> {
+        if Self::is_zst() {
+            return Ok(());
+        }
         if need > self.capacity {
-            let new_cap = usize::max(self.capacity * 2, 16);
-            self.realloc(new_cap);
+            let doubled = self.capacity.saturating_mul(2);
+            let new_cap = usize::max(usize::max(doubled, 16), need);
+            self.try_realloc(new_cap)?;
         }
+        Ok(())
     }
 
-    fn realloc(&mut self, new_cap: usize) {
+    fn try_realloc(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        if Self::is_zst() {
+            return Ok(());
+        }
+        let layout =
+            Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
         let new_ptr = self
             .alloc
-            .allocate(Layout::array::<T>(new_cap).expect("layout error"))
-            .expect("alloc error");
+            .allocate(layout)
+            .map_err(|_| TryReserveError::AllocError { layout })?;
         if self.capacity > 0 {
             unsafe {
                 std::ptr::copy_nonoverlapping(
@@ -92,6 +267,7 @@ where
         }
         self.capacity = new_cap;
         self.ptr = new_ptr.cast();
+        Ok(())
     }
 
     pub fn as_slice(&self) -> &[T] {
@@ -102,8 +278,115 @@ where
         self
     }
 
-    pub fn reserve(&mut self, n: usize) {
-        self.realloc(n);
+    /// Returns a raw pointer to the buffer, or a dangling, well-aligned
+    /// pointer if no allocation has happened yet.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the buffer, or a dangling,
+    /// well-aligned pointer if no allocation has happened yet.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    /// Ensures the vector can hold at least `additional` more elements
+    /// beyond its current length, growing amortized (like `push` does) and
+    /// never shrinking the existing capacity.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).expect("alloc error")
+    }
+
+    /// Like [`reserve`](Self::reserve), but does not over-allocate: the
+    /// resulting capacity is exactly `len + additional` (unless it is
+    /// already sufficient), and it never shrinks the existing capacity.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.try_reserve_exact(additional).expect("alloc error")
+    }
+
+    /// Like [`reserve`](Self::reserve), but reports allocation failure instead
+    /// of panicking. `additional` is the number of extra elements the vector
+    /// should be able to hold beyond its current length.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        self.try_grow(required)
+    }
+
+    /// Like [`try_reserve`](Self::try_reserve), but does not over-allocate:
+    /// the resulting capacity is exactly `len + additional` (unless it is
+    /// already sufficient).
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if required > self.capacity {
+            self.try_realloc(required)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the elements in `range`, returning them as an iterator.
+    ///
+    /// The removed elements are yielded as the `Drain` iterator runs, and the
+    /// remaining tail of the vector is shifted down to close the gap once
+    /// `Drain` is dropped, whether or not it was fully consumed.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len;
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "drain start is after drain end");
+        assert!(end <= len, "drain end is out of bounds");
+
+        // Shrink the vector's visible length to `start` for the duration of
+        // the drain so that a leaked `Drain` (e.g. via `mem::forget`) cannot
+        // cause the vector to double-drop or read past the removed range.
+        self.len = start;
+
+        Drain {
+            tail_start: end,
+            tail_len: len - end,
+            iter: unsafe { from_raw_parts(self.ptr.as_ptr().add(start), end - start) }.iter(),
+            vec: NonNull::from(self),
+        }
+    }
+
+    /// Removes and yields every element for which `pred` returns `true`.
+    ///
+    /// Elements that are kept are shifted down to close the gaps left by
+    /// removed ones. If the returned iterator is dropped before being fully
+    /// consumed, the still-unvisited elements are kept in the vector.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, A>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let old_len = self.len;
+        // See `drain`: hiding the elements behind `len = 0` keeps a leaked
+        // iterator from exposing half-shifted or already-yielded elements.
+        self.len = 0;
+        ExtractIf {
+            vec: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred,
+        }
     }
 }
 
@@ -156,11 +439,20 @@ where
     }
 }
 
-impl<T, A> Drop for Vek<T, A>
-where
-    A: Allocator,
-{
-    #[inline]
+/// Frees the backing allocation on drop, independently of whether the
+/// elements it held have been dropped yet.
+///
+/// Keeping this as a separate guard means the allocation is still released
+/// even if one of `T`'s destructors panics while we're dropping the elements
+/// in [`Vek`]'s own `Drop` impl: unwinding drops this guard and the memory is
+/// not leaked.
+struct DeallocGuard<'a, T, A: Allocator> {
+    ptr: NonNull<T>,
+    capacity: usize,
+    alloc: &'a A,
+}
+
+impl<'a, T, A: Allocator> Drop for DeallocGuard<'a, T, A> {
     fn drop(&mut self) {
         if self.capacity > 0 {
             unsafe {
@@ -173,6 +465,25 @@ where
     }
 }
 
+impl<T, A> Drop for Vek<T, A>
+where
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        let _guard = DeallocGuard {
+            ptr: self.ptr,
+            capacity: self.capacity,
+            alloc: &self.alloc,
+        };
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                self.ptr.as_ptr(),
+                self.len,
+            ));
+        }
+    }
+}
+
 impl<T, A> Deref for Vek<T, A>
 where
     A: Allocator,
@@ -214,9 +525,317 @@ impl<'v, T, A: Allocator> IntoIterator for &'v mut Vek<T, A> {
     }
 }
 
+/// An iterator that moves out of a [`Vek`], taking ownership of its elements
+/// and its backing allocation.
+///
+/// Created by the `IntoIterator` impl on `Vek<T, A>` (e.g. via a `for` loop
+/// over `vek`).
+pub struct IntoIter<T, A: Allocator = Global> {
+    buf: NonNull<T>,
+    cap: usize,
+    ptr: *const T,
+    end: *const T,
+    alloc: A,
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.ptr as *const u8 == self.end as *const u8 {
+            return None;
+        }
+        unsafe {
+            let old = self.ptr;
+            self.ptr = if mem::size_of::<T>() == 0 {
+                (self.ptr as *const u8).wrapping_add(1) as *const T
+            } else {
+                self.ptr.add(1)
+            };
+            Some(old.read())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.ptr as *const u8 == self.end as *const u8 {
+            return None;
+        }
+        unsafe {
+            self.end = if mem::size_of::<T>() == 0 {
+                (self.end as *const u8).wrapping_sub(1) as *const T
+            } else {
+                self.end.sub(1)
+            };
+            Some(self.end.read())
+        }
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
+    fn len(&self) -> usize {
+        if mem::size_of::<T>() == 0 {
+            (self.end as usize) - (self.ptr as usize)
+        } else {
+            unsafe { self.end.offset_from(self.ptr) as usize }
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        let _guard = DeallocGuard {
+            ptr: self.buf,
+            capacity: self.cap,
+            alloc: &self.alloc,
+        };
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                self.ptr as *mut T,
+                self.len(),
+            ));
+        }
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for Vek<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let this = mem::ManuallyDrop::new(self);
+        let buf = this.ptr;
+        let cap = this.capacity;
+        let len = this.len;
+        let alloc = unsafe { std::ptr::read(&this.alloc) };
+        let start = buf.as_ptr() as *const T;
+        let end = if mem::size_of::<T>() == 0 {
+            (start as *const u8).wrapping_add(len) as *const T
+        } else {
+            unsafe { start.add(len) }
+        };
+        IntoIter {
+            buf,
+            cap,
+            ptr: start,
+            end,
+            alloc,
+        }
+    }
+}
+
+/// An iterator that removes a range of elements from a [`Vek`], yielding
+/// them by value, and closes the resulting gap when dropped.
+///
+/// Created by [`Vek::drain`].
+pub struct Drain<'a, T, A: Allocator = Global> {
+    tail_start: usize,
+    tail_len: usize,
+    iter: Iter<'a, T>,
+    vec: NonNull<Vek<T, A>>,
+}
+
+impl<'a, T, A: Allocator> Iterator for Drain<'a, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|elt| unsafe { std::ptr::read(elt) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, A: Allocator> DoubleEndedIterator for Drain<'a, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        self.iter
+            .next_back()
+            .map(|elt| unsafe { std::ptr::read(elt) })
+    }
+}
+
+impl<'a, T, A: Allocator> ExactSizeIterator for Drain<'a, T, A> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, T, A: Allocator> Drop for Drain<'a, T, A> {
+    fn drop(&mut self) {
+        // Ensures the tail is moved back into place even if dropping one of
+        // the not-yet-yielded elements below panics.
+        struct TailGuard<'r, 'a, T, A: Allocator>(&'r mut Drain<'a, T, A>);
+
+        impl<'r, 'a, T, A: Allocator> Drop for TailGuard<'r, 'a, T, A> {
+            fn drop(&mut self) {
+                if self.0.tail_len > 0 {
+                    unsafe {
+                        let vek = self.0.vec.as_mut();
+                        let start = vek.len;
+                        if self.0.tail_start != start {
+                            let src = vek.ptr.as_ptr().add(self.0.tail_start);
+                            let dst = vek.ptr.as_ptr().add(start);
+                            std::ptr::copy(src, dst, self.0.tail_len);
+                        }
+                        vek.len = start + self.0.tail_len;
+                    }
+                }
+            }
+        }
+
+        let guard = TailGuard(self);
+        guard.0.for_each(drop);
+    }
+}
+
+/// An iterator that removes elements matching a predicate from a [`Vek`],
+/// yielding the removed elements.
+///
+/// Created by [`Vek::extract_if`].
+pub struct ExtractIf<'a, T, F, A: Allocator = Global>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    vec: &'a mut Vek<T, A>,
+    idx: usize,
+    del: usize,
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, F, A: Allocator> Iterator for ExtractIf<'a, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.old_len {
+                let i = self.idx;
+                let slice = from_raw_parts_mut(self.vec.ptr.as_ptr(), self.old_len);
+                let keep = !(self.pred)(&mut slice[i]);
+                self.idx += 1;
+                if !keep {
+                    self.del += 1;
+                    return Some(std::ptr::read(&slice[i]));
+                } else if self.del > 0 {
+                    let base = self.vec.ptr.as_ptr();
+                    let src = base.add(i);
+                    let dst = src.sub(self.del);
+                    std::ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<'a, T, F, A: Allocator> Drop for ExtractIf<'a, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let base = self.vec.ptr.as_ptr();
+                let src = base.add(self.idx);
+                let dst = src.sub(self.del);
+                std::ptr::copy(src, dst, self.old_len - self.idx);
+            }
+            self.vec.len = self.old_len - self.del;
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Vek<T, Global> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let mut vek = Vek::with_capacity_in(lower, Global);
+        vek.extend(iter);
+        vek
+    }
+}
+
+impl<T, A> Extend<T> for Vek<T, A>
+where
+    A: Allocator,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        if lower > 0 {
+            self.reserve(lower);
+        }
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// Creates a [`Vek`], preallocating capacity up front from the number of
+/// elements given, the same way [`vec!`] does for `Vec`.
+///
+/// ```
+/// # #![feature(allocator_api)]
+/// # use vektor::vek;
+/// let v = vek![1, 2, 3];
+/// assert_eq!(v.as_slice(), &[1, 2, 3]);
+///
+/// let zeros = vek![0; 4];
+/// assert_eq!(zeros.as_slice(), &[0, 0, 0, 0]);
+/// ```
+#[macro_export]
+macro_rules! vek {
+    () => {
+        $crate::Vek::new()
+    };
+    ($elem:expr; $n:expr) => {{
+        let count = $n;
+        let elem = $elem;
+        let mut vek = $crate::Vek::with_capacity_in(count, ::std::alloc::Global);
+        for _ in 0..count {
+            vek.push(::std::clone::Clone::clone(&elem));
+        }
+        vek
+    }};
+    ($($elem:expr),+ $(,)?) => {{
+        let elems = [$($elem),+];
+        let mut vek = $crate::Vek::with_capacity_in(elems.len(), ::std::alloc::Global);
+        for elem in elems {
+            vek.push(elem);
+        }
+        vek
+    }};
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Vek;
+    use super::{TryReserveError, Vek};
+    use std::alloc::Global;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Counts how many times it has been dropped via a shared counter, for
+    /// tests that need to observe whether/how many destructors ran.
+    struct CountDrops(Rc<Cell<usize>>);
+    impl Drop for CountDrops {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
 
     #[test]
     #[should_panic]
@@ -263,6 +882,451 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_push_should_work() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..17 {
+            assert_eq!(v.try_push(i * i), Ok(()));
+        }
+        assert_eq!(v.len(), 17);
+        for i in 0..17 {
+            assert_eq!(v[i], (i * i) as u32);
+        }
+    }
+
+    #[test]
+    fn try_push_hands_the_value_back_on_allocation_failure() {
+        use std::ptr::NonNull;
+
+        let huge_cap = usize::MAX / 2 + 10;
+        let mut v: Vek<u8> =
+            unsafe { Vek::from_raw_parts(NonNull::dangling().as_ptr(), huge_cap, huge_cap) };
+
+        assert_eq!(v.try_push(42), Err(42));
+
+        std::mem::forget(v);
+    }
+
+    #[test]
+    fn try_reserve_grows_capacity() {
+        let mut v = Vek::<u32>::new();
+        assert_eq!(v.try_reserve(10), Ok(()));
+        assert!(v.capacity() >= 10);
+
+        v.push(1);
+        let cap = v.capacity();
+        assert_eq!(v.try_reserve(1), Ok(()));
+        assert_eq!(v.capacity(), cap);
+    }
+
+    #[test]
+    fn try_reserve_exact_does_not_over_allocate() {
+        let mut v = Vek::<u32>::new();
+        assert_eq!(v.try_reserve_exact(5), Ok(()));
+        assert_eq!(v.capacity(), 5);
+    }
+
+    #[test]
+    fn try_reserve_reports_capacity_overflow() {
+        let mut v = Vek::<u32>::new();
+        v.push(1);
+        assert_eq!(
+            v.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn drops_all_elements_on_teardown() {
+        let count = Rc::new(Cell::new(0));
+        {
+            let mut v = Vek::<CountDrops>::new();
+            for _ in 0..20 {
+                v.push(CountDrops(count.clone()));
+            }
+        }
+        assert_eq!(count.get(), 20);
+    }
+
+    #[test]
+    fn drops_remaining_elements_if_one_panics() {
+        use std::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        struct PanicsOnThird {
+            index: usize,
+            dropped: Rc<Cell<usize>>,
+        }
+        impl Drop for PanicsOnThird {
+            fn drop(&mut self) {
+                self.dropped.set(self.dropped.get() + 1);
+                if self.index == 2 {
+                    panic!("boom");
+                }
+            }
+        }
+
+        let dropped = Rc::new(Cell::new(0));
+        let mut v = Vek::<PanicsOnThird>::new();
+        for index in 0..5 {
+            v.push(PanicsOnThird {
+                index,
+                dropped: dropped.clone(),
+            });
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| drop(v)));
+        assert!(result.is_err());
+        assert_eq!(dropped.get(), 5);
+    }
+
+    #[test]
+    fn zst_reports_unbounded_capacity_without_allocating() {
+        let mut v = Vek::<()>::new();
+        assert_eq!(v.capacity(), usize::MAX);
+        for _ in 0..1000 {
+            v.push(());
+        }
+        assert_eq!(v.len(), 1000);
+        assert_eq!(v.capacity(), usize::MAX);
+    }
+
+    #[test]
+    fn zst_with_drop_impl_runs_destructor_for_each_element() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Marker;
+        impl Drop for Marker {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        assert_eq!(std::mem::size_of::<Marker>(), 0);
+
+        {
+            let mut v = Vek::<Marker>::new();
+            for _ in 0..7 {
+                v.push(Marker);
+            }
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 7);
+    }
+
+    #[test]
+    fn zst_index_and_pop_work() {
+        let mut v = Vek::<()>::new();
+        v.push(());
+        v.push(());
+        assert_eq!(v[0], ());
+        assert_eq!(v.pop(), Some(()));
+        assert_eq!(v.len(), 1);
+    }
+
+    #[test]
+    fn owning_into_iter_yields_values_by_value() {
+        let mut v = Vek::<String>::new();
+        v.push("a".to_string());
+        v.push("b".to_string());
+        v.push("c".to_string());
+
+        let collected: Vec<String> = v.into_iter().collect();
+        assert_eq!(collected, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn owning_into_iter_drops_remaining_elements() {
+        let count = Rc::new(Cell::new(0));
+        let mut v = Vek::<CountDrops>::new();
+        for _ in 0..5 {
+            v.push(CountDrops(count.clone()));
+        }
+
+        let mut iter = v.into_iter();
+        iter.next();
+        iter.next();
+        drop(iter);
+
+        assert_eq!(count.get(), 5);
+    }
+
+    #[test]
+    fn owning_into_iter_supports_double_ended() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..5 {
+            v.push(i);
+        }
+        let mut iter = v.into_iter();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn drain_removes_and_yields_a_range() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..10 {
+            v.push(i);
+        }
+        let drained: Vec<u32> = v.drain(2..5).collect();
+        assert_eq!(drained, vec![2, 3, 4]);
+        assert_eq!(v.as_slice(), &[0, 1, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn drain_dropped_without_iterating_still_closes_the_gap() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..10 {
+            v.push(i);
+        }
+        drop(v.drain(2..5));
+        assert_eq!(v.as_slice(), &[0, 1, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn drain_full_range_empties_the_vek() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..4 {
+            v.push(i);
+        }
+        let drained: Vec<u32> = v.drain(..).collect();
+        assert_eq!(drained, vec![0, 1, 2, 3]);
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn extract_if_removes_matching_elements() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..10 {
+            v.push(i);
+        }
+        let evens: Vec<u32> = v.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, vec![0, 2, 4, 6, 8]);
+        assert_eq!(v.as_slice(), &[1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn extract_if_dropped_early_keeps_unvisited_elements() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..10 {
+            v.push(i);
+        }
+        {
+            let mut iter = v.extract_if(|x| *x % 2 == 0);
+            assert_eq!(iter.next(), Some(0));
+            assert_eq!(iter.next(), Some(2));
+        }
+        // 0 and 2 were removed before the iterator was dropped; everything
+        // from index 3 onward in the original vek (3..10) was never visited
+        // and stays untouched, just shifted down to close the gap.
+        assert_eq!(v.as_slice(), &[1, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn new_in_uses_the_given_allocator() {
+        let mut v = Vek::<u32, Global>::new_in(Global);
+        v.push(1);
+        v.push(2);
+        assert_eq!(v.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn with_capacity_in_preallocates() {
+        let v = Vek::<u32, Global>::with_capacity_in(8, Global);
+        assert_eq!(v.capacity(), 8);
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn with_capacity_in_zero_does_not_allocate() {
+        let v = Vek::<u32, Global>::with_capacity_in(0, Global);
+        assert_eq!(v.capacity(), 0);
+    }
+
+    #[test]
+    fn with_capacity_preallocates_on_the_global_allocator() {
+        let v = Vek::<u32>::with_capacity(8);
+        assert_eq!(v.capacity(), 8);
+        assert_eq!(v.len(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn spill_allocator_keeps_data_intact_past_the_budget() {
+        use crate::SpillAllocator;
+
+        // A tiny budget guarantees growth quickly spills past it.
+        let dir = std::env::temp_dir();
+        let alloc = SpillAllocator::new(&dir, 64);
+        assert_eq!(alloc.spill_dir(), dir);
+        let mut v = Vek::<u64, SpillAllocator>::new_in(alloc);
+        for i in 0..500u64 {
+            v.push(i);
+        }
+        for i in 0..500u64 {
+            assert_eq!(v[i as usize], i);
+        }
+    }
+
+    #[test]
+    fn from_iterator_collects_elements() {
+        let v: Vek<u32> = (0..20).collect();
+        assert_eq!(v.len(), 20);
+        for i in 0..20 {
+            assert_eq!(v[i], i as u32);
+        }
+    }
+
+    #[test]
+    fn extend_appends_without_losing_existing_elements() {
+        let mut v = Vek::<u32>::new();
+        v.push(1);
+        v.push(2);
+        v.extend(vec![3, 4, 5]);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn extend_reserves_based_on_the_size_hint_not_len_plus_size_hint() {
+        let mut v = Vek::<u32>::new();
+        v.reserve_exact(10);
+        for i in 0..10 {
+            v.push(i);
+        }
+        // A correct `self.reserve(lower)` (lower == 5) amortizes to 20, not
+        // the 25 a buggy `self.reserve(self.len + lower)` would produce.
+        v.extend(vec![1, 2, 3, 4, 5]);
+        assert_eq!(v.capacity(), 20);
+    }
+
+    #[test]
+    fn vek_macro_empty() {
+        let v: Vek<u32> = vek![];
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn vek_macro_list() {
+        let v = vek![1, 2, 3];
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        assert_eq!(v.capacity(), 3);
+    }
+
+    #[test]
+    fn vek_macro_repeat() {
+        let v = vek![7; 4];
+        assert_eq!(v.as_slice(), &[7, 7, 7, 7]);
+        assert_eq!(v.capacity(), 4);
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..5 {
+            v.push(i);
+        }
+        let (ptr, len, capacity) = v.into_raw_parts();
+
+        let mut rebuilt = unsafe { Vek::from_raw_parts(ptr, len, capacity) };
+        assert_eq!(rebuilt.as_slice(), &[0, 1, 2, 3, 4]);
+        rebuilt.push(5);
+        assert_eq!(rebuilt.as_slice(), &[0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn into_raw_parts_does_not_drop_elements() {
+        let count = Rc::new(Cell::new(0));
+        let mut v = Vek::<CountDrops>::new();
+        for _ in 0..3 {
+            v.push(CountDrops(count.clone()));
+        }
+        let (ptr, len, capacity) = v.into_raw_parts();
+        assert_eq!(count.get(), 0);
+
+        // Reclaim it so the elements and allocation are still freed.
+        drop(unsafe { Vek::from_raw_parts(ptr, len, capacity) });
+        assert_eq!(count.get(), 3);
+    }
+
+    #[test]
+    fn raw_parts_in_round_trip_with_a_custom_allocator() {
+        let mut v = Vek::<u32, Global>::new_in(Global);
+        for i in 0..5 {
+            v.push(i);
+        }
+        let (ptr, len, capacity, alloc) = v.into_raw_parts_in();
+
+        let mut rebuilt = unsafe { Vek::from_raw_parts_in(ptr, len, capacity, alloc) };
+        assert_eq!(rebuilt.as_slice(), &[0, 1, 2, 3, 4]);
+        rebuilt.push(5);
+        assert_eq!(rebuilt.as_slice(), &[0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn as_ptr_and_as_mut_ptr_point_at_the_buffer() {
+        let mut v = Vek::<u32>::new();
+        v.push(1);
+        v.push(2);
+        unsafe {
+            assert_eq!(*v.as_ptr(), 1);
+            *v.as_mut_ptr().add(1) = 9;
+        }
+        assert_eq!(v.as_slice(), &[1, 9]);
+    }
+
+    #[test]
+    fn capacity_doubling_overflow_reports_capacity_overflow_instead_of_panicking() {
+        use std::ptr::NonNull;
+
+        // A capacity just past `usize::MAX / 2` makes naive `capacity * 2`
+        // wrap around; `try_grow` must saturate instead and still fail the
+        // allocation cleanly rather than compute a bogus, too-small layout.
+        let huge_cap = usize::MAX / 2 + 10;
+        let mut v: Vek<u8> =
+            unsafe { Vek::from_raw_parts(NonNull::dangling().as_ptr(), huge_cap, huge_cap) };
+
+        assert_eq!(v.try_reserve(1), Err(TryReserveError::CapacityOverflow));
+
+        // `v` never held a real allocation; forget it instead of letting it
+        // try to deallocate the dangling pointer above.
+        std::mem::forget(v);
+    }
+
+    #[test]
+    fn reserve_is_additive_and_never_shrinks() {
+        let mut v = Vek::<u32>::new();
+        for i in 0..16 {
+            v.push(i);
+        }
+        assert_eq!(v.capacity(), 16);
+
+        // Requesting an `additional` smaller than the current capacity must
+        // not shrink the allocation out from under its elements.
+        v.reserve(1);
+        assert!(v.capacity() >= 16);
+        assert_eq!(v.as_slice(), &(0..16).collect::<Vec<_>>()[..]);
+
+        v.reserve(100);
+        assert!(v.capacity() >= 116);
+        assert_eq!(v.len(), 16);
+    }
+
+    #[test]
+    fn reserve_exact_sizes_to_len_plus_additional() {
+        let mut v = Vek::<u32>::new();
+        v.reserve_exact(4);
+        assert_eq!(v.capacity(), 4);
+
+        // Calling it again with a smaller `additional` must not shrink.
+        v.reserve_exact(1);
+        assert_eq!(v.capacity(), 4);
+    }
+
     #[test]
     fn mut_index_should_work() {
         let mut v = Vek::<u32>::new();
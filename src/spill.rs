@@ -0,0 +1,163 @@
+//! A memory-budgeted [`Allocator`] that spills past-budget allocations to a
+//! shared memory mapping of an unlinked temporary file instead of growing
+//! resident memory without bound.
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::ffi::c_void;
+use std::fs::OpenOptions;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const PROT_READ: i32 = 1;
+const PROT_WRITE: i32 = 2;
+const MAP_SHARED: i32 = 1;
+const MAP_FAILED: *mut c_void = -1isize as *mut c_void;
+
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        length: usize,
+        prot: i32,
+        flags: i32,
+        fd: i32,
+        offset: i64,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, length: usize) -> i32;
+}
+
+struct SpillRegion {
+    addr: usize,
+    len: usize,
+}
+
+/// An [`Allocator`] that keeps allocations in ordinary heap memory up to a
+/// configured byte budget, then "spills" anything beyond that to a temporary
+/// file mapped into the process's address space with `mmap`.
+///
+/// The backing file is unlinked as soon as it is created, so the spilled
+/// pages are reclaimed by the OS the moment they're unmapped, without
+/// leaving anything behind on disk. Spilled memory is paged in and out like
+/// any other file-backed mapping, so growing a [`crate::Vek`] past the
+/// budget keeps working, just more slowly, instead of failing outright.
+pub struct SpillAllocator {
+    budget_bytes: usize,
+    spill_dir: PathBuf,
+    resident_bytes: AtomicUsize,
+    regions: Mutex<Vec<SpillRegion>>,
+}
+
+impl SpillAllocator {
+    /// Creates an allocator that keeps up to `budget_bytes` resident in
+    /// normal heap memory before spilling further allocations to temporary
+    /// files created under `spill_dir`.
+    pub fn new(spill_dir: impl AsRef<Path>, budget_bytes: usize) -> Self {
+        SpillAllocator {
+            budget_bytes,
+            spill_dir: spill_dir.as_ref().to_path_buf(),
+            resident_bytes: AtomicUsize::new(0),
+            regions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The directory spilled allocations are backed by.
+    pub fn spill_dir(&self) -> &Path {
+        &self.spill_dir
+    }
+
+    /// Bytes currently served from ordinary heap memory (excludes spilled
+    /// allocations).
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes.load(Ordering::Relaxed)
+    }
+
+    fn would_exceed_budget(&self, additional: usize) -> bool {
+        self.resident_bytes() + additional > self.budget_bytes
+    }
+
+    fn spill(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = self.spill_dir.join(format!(
+            "vektor-spill-{}-{}",
+            std::process::id(),
+            id
+        ));
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|_| AllocError)?;
+        // Unlink right away: the open fd keeps the backing store alive for
+        // as long as the mapping exists, and nothing is left on disk.
+        let _ = std::fs::remove_file(&path);
+        file.set_len(layout.size() as u64).map_err(|_| AllocError)?;
+
+        let addr = unsafe {
+            mmap(
+                std::ptr::null_mut(),
+                layout.size(),
+                PROT_READ | PROT_WRITE,
+                MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if addr == MAP_FAILED {
+            return Err(AllocError);
+        }
+
+        self.regions.lock().unwrap().push(SpillRegion {
+            addr: addr as usize,
+            len: layout.size(),
+        });
+
+        let slice = std::ptr::slice_from_raw_parts_mut(addr as *mut u8, layout.size());
+        NonNull::new(slice).ok_or(AllocError)
+    }
+
+    fn unspill(&self, addr: usize) -> bool {
+        let mut regions = self.regions.lock().unwrap();
+        if let Some(index) = regions.iter().position(|region| region.addr == addr) {
+            let region = regions.swap_remove(index);
+            unsafe {
+                munmap(region.addr as *mut c_void, region.len);
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+unsafe impl Allocator for SpillAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Global.allocate(layout);
+        }
+        if self.would_exceed_budget(layout.size()) {
+            return self.spill(layout);
+        }
+        let allocated = Global.allocate(layout)?;
+        self.resident_bytes
+            .fetch_add(layout.size(), Ordering::Relaxed);
+        Ok(allocated)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+        if self.unspill(ptr.as_ptr() as usize) {
+            return;
+        }
+        self.resident_bytes
+            .fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}